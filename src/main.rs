@@ -1,10 +1,15 @@
-use chrono::{DateTime, Utc};
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use clap::Parser;
-use regex::Regex;
+use regex::{Regex, RegexBuilder, RegexSet, RegexSetBuilder};
+use std::collections::{HashSet, VecDeque};
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, IsTerminal};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 #[derive(Parser, Debug)]
 #[clap(
@@ -14,13 +19,15 @@ use std::time::SystemTime;
     long_about = None
 )]
 struct Args {
-    /// Pattern to search for (supports regex and simple glob patterns)
+    /// Pattern(s) to search for (supports regex and simple glob patterns)
     #[clap(
         short,
         long,
-        help = "The search pattern. Supports regex and glob patterns like *.rs"
+        required = true,
+        multiple_occurrences = true,
+        help = "The search pattern. Supports regex and glob patterns like *.rs. Repeat --pattern or pass a comma-separated list to match any of several patterns"
     )]
-    pattern: String,
+    pattern: Vec<String>,
 
     /// Directory to start the search from
     #[clap(
@@ -36,8 +43,8 @@ struct Args {
     date: bool,
 
     /// Show file size
-    #[clap(short, long, help = "Display the size of found files")]
-    size: bool,
+    #[clap(long = "show-size", help = "Display the size of found files")]
+    show_size: bool,
 
     /// Use human-readable file sizes
     #[clap(
@@ -58,115 +65,1012 @@ struct Args {
         help = "Search for the pattern within file contents"
     )]
     content_search: bool,
+
+    /// Number of worker threads used to walk the directory tree
+    #[clap(
+        short = 'j',
+        long,
+        help = "Number of threads to use for traversal (defaults to the number of available CPUs)"
+    )]
+    threads: Option<usize>,
+
+    /// Run a command for every match
+    #[clap(
+        short = 'x',
+        long = "exec",
+        help = "Execute a command for each matched file. Supports the placeholders {} (full path), {/} (basename), {//} (parent dir), {.} (path without extension) and {/.} (basename without extension); the path is appended if none are present"
+    )]
+    exec: Option<String>,
+
+    /// Run a command once with every match appended
+    #[clap(
+        short = 'X',
+        long = "exec-batch",
+        help = "Execute a command once with all matched files appended as arguments (or substituted at a single {} placeholder)"
+    )]
+    exec_batch: Option<String>,
+
+    /// Filter by file size
+    #[clap(
+        long = "size",
+        help = "Filter by size, e.g. +10M (at least 10 MiB), -500k (at most 500 KiB), 1G (exactly 1 GiB). Can be repeated; all constraints must hold"
+    )]
+    size: Vec<String>,
+
+    /// Only include files modified within the given duration
+    #[clap(
+        long = "changed-within",
+        help = "Only include files modified within the given duration (e.g. 2d, 3h) or since the given date (e.g. 2024-01-01)"
+    )]
+    changed_within: Option<String>,
+
+    /// Only include files modified before the given duration or date
+    #[clap(
+        long = "changed-before",
+        help = "Only include files modified before the given duration (e.g. 2d, 3h) or date (e.g. 2024-01-01)"
+    )]
+    changed_before: Option<String>,
+
+    /// Include hidden (dot) files and directories
+    #[clap(
+        short = 'a',
+        long,
+        help = "Include hidden files and directories (dotfiles are skipped by default)"
+    )]
+    hidden: bool,
+
+    /// Disable .gitignore/.ignore processing
+    #[clap(
+        short = 'I',
+        long = "no-ignore",
+        help = "Do not respect .gitignore/.ignore files; walk every directory"
+    )]
+    no_ignore: bool,
+
+    /// Restrict results to one or more entry kinds
+    #[clap(
+        short = 't',
+        long = "type",
+        possible_values = &["f", "d", "l", "x", "e"],
+        multiple_occurrences = true,
+        help = "Only match entries of the given type: f (file), d (directory), l (symlink), x (executable), e (empty). Can be repeated"
+    )]
+    file_type: Vec<String>,
+
+    /// Lines of context to show before each content match
+    #[clap(
+        short = 'B',
+        long = "before-context",
+        default_value = "0",
+        help = "Show N lines of context before each content match"
+    )]
+    before_context: usize,
+
+    /// Lines of context to show after each content match
+    #[clap(
+        short = 'A',
+        long = "after-context",
+        default_value = "0",
+        help = "Show N lines of context after each content match"
+    )]
+    after_context: usize,
+
+    /// Lines of context to show before and after each content match
+    #[clap(
+        short = 'C',
+        long = "context",
+        help = "Show N lines of context before and after each content match (overrides -B/-A)"
+    )]
+    context: Option<usize>,
+
+    /// Search binary files too
+    #[clap(
+        long = "text",
+        help = "Also search files that look binary (contain a NUL byte); skipped by default"
+    )]
+    text: bool,
+
+    /// Force case-insensitive matching
+    #[clap(
+        short = 'i',
+        long = "ignore-case",
+        help = "Force case-insensitive matching, overriding the smart-case default"
+    )]
+    ignore_case: bool,
+
+    /// Force case-sensitive matching
+    #[clap(
+        short = 's',
+        long = "case-sensitive",
+        help = "Force case-sensitive matching, overriding the smart-case default"
+    )]
+    case_sensitive: bool,
+
+    /// When to colorize output
+    #[clap(
+        long,
+        default_value = "auto",
+        possible_values = &["auto", "always", "never"],
+        help = "Colorize output using LS_COLORS: auto only colorizes when stdout is a terminal"
+    )]
+    color: String,
+}
+
+/// Returns whether `pattern` contains an uppercase letter outside of a
+/// regex escape sequence (e.g. `\W`, `\D`), used to decide smart-case
+/// matching: a pattern with no "real" uppercase letters is matched
+/// case-insensitively.
+fn pattern_has_uppercase_char(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            chars.next();
+            continue;
+        }
+        if c.is_uppercase() {
+            return true;
+        }
+    }
+    false
+}
+
+/// A single `--size` constraint: at least, at most, or exactly the given
+/// number of bytes.
+#[derive(Debug, Clone, Copy)]
+enum SizeBound {
+    AtLeast(u64),
+    AtMost(u64),
+    Exact(u64),
+}
+
+/// Size and modification-time constraints evaluated against every candidate
+/// file before it is recorded as a match.
+#[derive(Debug, Clone, Default)]
+struct Filters {
+    size_bounds: Vec<SizeBound>,
+    changed_within: Option<SystemTime>,
+    changed_before: Option<SystemTime>,
+    types: Vec<char>,
+}
+
+impl Filters {
+    fn passes(&self, path: &Path, metadata: &fs::Metadata) -> bool {
+        let size = metadata.len();
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+
+        for bound in &self.size_bounds {
+            let ok = match bound {
+                SizeBound::AtLeast(n) => size >= *n,
+                SizeBound::AtMost(n) => size <= *n,
+                SizeBound::Exact(n) => size == *n,
+            };
+            if !ok {
+                return false;
+            }
+        }
+
+        if let Some(threshold) = self.changed_within {
+            if modified < threshold {
+                return false;
+            }
+        }
+
+        if let Some(threshold) = self.changed_before {
+            if modified > threshold {
+                return false;
+            }
+        }
+
+        if !self.types.is_empty() && !self.types.iter().any(|t| matches_type(*t, path, metadata)) {
+            return false;
+        }
+
+        true
+    }
+}
+
+/// Checks whether `path` matches a single `--type` character: `f` regular
+/// file, `d` directory, `l` symlink (checked via `symlink_metadata` so the
+/// link itself is inspected rather than its target), `x` executable, `e`
+/// empty file or directory.
+fn matches_type(type_char: char, path: &Path, metadata: &fs::Metadata) -> bool {
+    match type_char {
+        'f' => metadata.is_file(),
+        'd' => metadata.is_dir(),
+        'l' => fs::symlink_metadata(path)
+            .map(|m| m.file_type().is_symlink())
+            .unwrap_or(false),
+        'x' => is_executable(metadata),
+        'e' => is_empty_entry(path, metadata),
+        _ => false,
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+fn is_empty_entry(path: &Path, metadata: &fs::Metadata) -> bool {
+    if metadata.is_dir() {
+        fs::read_dir(path)
+            .map(|mut entries| entries.next().is_none())
+            .unwrap_or(false)
+    } else {
+        metadata.len() == 0
+    }
+}
+
+/// ANSI SGR code used to dim size/date annotations.
+const DIM_CODE: &str = "2";
+
+/// ANSI SGR code used to highlight the matched portion of a content search
+/// line, matching the bold red `grep --color` convention.
+const HIGHLIGHT_CODE: &str = "1;31";
+
+/// Color rules parsed from `LS_COLORS`: a code for directories, one for
+/// symlinks, one for executables, and a per-extension lookup, matching the
+/// precedence `ls` itself uses (file type before extension).
+#[derive(Debug, Default)]
+struct LsColors {
+    dir: Option<String>,
+    symlink: Option<String>,
+    executable: Option<String>,
+    by_extension: std::collections::HashMap<String, String>,
+}
+
+impl LsColors {
+    fn parse(value: &str) -> Self {
+        let mut colors = LsColors::default();
+
+        for entry in value.split(':') {
+            let mut parts = entry.splitn(2, '=');
+            let key = match parts.next() {
+                Some(key) if !key.is_empty() => key,
+                _ => continue,
+            };
+            let code = match parts.next() {
+                Some(code) if !code.is_empty() => code,
+                _ => continue,
+            };
+
+            if let Some(ext) = key.strip_prefix("*.") {
+                colors
+                    .by_extension
+                    .insert(ext.to_ascii_lowercase(), code.to_string());
+            } else {
+                match key {
+                    "di" => colors.dir = Some(code.to_string()),
+                    "ln" => colors.symlink = Some(code.to_string()),
+                    "ex" => colors.executable = Some(code.to_string()),
+                    _ => {}
+                }
+            }
+        }
+
+        colors
+    }
+
+    /// Picks the ANSI code for `path`, checking file type (directory, then
+    /// symlink, then executable) before falling back to its extension.
+    fn code_for(&self, path: &Path) -> Option<&str> {
+        if path.is_dir() {
+            return self.dir.as_deref();
+        }
+        if fs::symlink_metadata(path)
+            .map(|m| m.file_type().is_symlink())
+            .unwrap_or(false)
+        {
+            return self.symlink.as_deref();
+        }
+        if fs::metadata(path).map(|m| is_executable(&m)).unwrap_or(false) {
+            return self.executable.as_deref();
+        }
+
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        self.by_extension.get(&ext).map(|s| s.as_str())
+    }
+}
+
+/// Wraps `text` in the ANSI escape sequence for `code`.
+fn paint(text: &str, code: &str) -> String {
+    format!("\x1b[{}m{}\x1b[0m", code, text)
+}
+
+/// Parses a size expression such as `+10M`, `-500k` or `1G` into a
+/// [`SizeBound`], using the same 1024-based units as `human_readable_size`.
+fn parse_size_expr(expr: &str) -> Option<SizeBound> {
+    let (sign, rest) = match expr.as_bytes().first() {
+        Some(b'+') => (Some(1), &expr[1..]),
+        Some(b'-') => (Some(-1), &expr[1..]),
+        Some(b'=') => (None, &expr[1..]),
+        _ => (None, expr),
+    };
+
+    let digits_end = rest
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(rest.len());
+    let (number, unit) = rest.split_at(digits_end);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier = match unit.to_ascii_uppercase().trim_end_matches('B') {
+        "" => 1.0,
+        "K" => 1024.0,
+        "M" => 1024f64.powi(2),
+        "G" => 1024f64.powi(3),
+        "T" => 1024f64.powi(4),
+        "P" => 1024f64.powi(5),
+        _ => return None,
+    };
+
+    let bytes = (number * multiplier).round() as u64;
+
+    Some(match sign {
+        Some(1) => SizeBound::AtLeast(bytes),
+        Some(-1) => SizeBound::AtMost(bytes),
+        _ => SizeBound::Exact(bytes),
+    })
+}
+
+/// Parses either a relative duration (`2d`, `3h`, `45m`, `30s`, `1w`) or an
+/// absolute `YYYY-MM-DD` date into an absolute point in time.
+fn parse_time_expr(expr: &str) -> Option<SystemTime> {
+    if let Some(duration) = parse_relative_duration(expr) {
+        return SystemTime::now().checked_sub(duration);
+    }
+
+    let date = NaiveDate::parse_from_str(expr, "%Y-%m-%d").ok()?;
+    let datetime = date.and_hms_opt(0, 0, 0)?;
+    Some(SystemTime::from(Utc.from_utc_datetime(&datetime)))
+}
+
+fn parse_relative_duration(expr: &str) -> Option<Duration> {
+    let digits_end = expr.find(|c: char| !c.is_ascii_digit())?;
+    if digits_end == 0 {
+        return None;
+    }
+    let (number, unit) = expr.split_at(digits_end);
+    let number: u64 = number.parse().ok()?;
+
+    let seconds = match unit {
+        "s" => number,
+        "m" => number * 60,
+        "h" => number * 3600,
+        "d" => number * 86400,
+        "w" => number * 604800,
+        _ => return None,
+    };
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// Builds the [`Filters`] evaluated during traversal from the raw CLI
+/// arguments, exiting with an error message if any expression is malformed.
+fn build_filters(args: &Args) -> Filters {
+    let size_bounds = args
+        .size
+        .iter()
+        .map(|expr| {
+            parse_size_expr(expr).unwrap_or_else(|| {
+                eprintln!("Invalid --size expression: {}", expr);
+                std::process::exit(1);
+            })
+        })
+        .collect();
+
+    let parse_threshold = |flag: &str, expr: &str| {
+        parse_time_expr(expr).unwrap_or_else(|| {
+            eprintln!("Invalid {} expression: {}", flag, expr);
+            std::process::exit(1);
+        })
+    };
+
+    let types = args
+        .file_type
+        .iter()
+        .map(|s| s.chars().next().unwrap_or('f'))
+        .collect();
+
+    Filters {
+        size_bounds,
+        changed_within: args
+            .changed_within
+            .as_deref()
+            .map(|expr| parse_threshold("--changed-within", expr)),
+        changed_before: args
+            .changed_before
+            .as_deref()
+            .map(|expr| parse_threshold("--changed-before", expr)),
+        types,
+    }
 }
 #[derive(Debug)]
 struct FileInfo {
     path: PathBuf,
     size: u64,
     modified: SystemTime,
-    matches_content: bool,
+    content_matches: Vec<ContentMatch>,
+}
+
+/// A single line surfaced by a content search: either the matching line
+/// itself or one of its `-B`/`-A`/`-C` context lines. `match_range` is the
+/// byte range of the first pattern occurrence, set only on matching lines,
+/// used to highlight it in the display loop.
+#[derive(Debug, Clone)]
+struct ContentMatch {
+    line_no: usize,
+    text: String,
+    is_match: bool,
+    match_range: Option<(usize, usize)>,
+}
+
+/// A single compiled `.gitignore`/`.ignore` rule. Later rules override
+/// earlier ones, and a `negate` rule re-includes a path an earlier rule
+/// excluded, mirroring git's own precedence. A rule with a slash anywhere but
+/// the end (`anchored`) is matched against the entry's path relative to
+/// `base` (the directory containing the ignore file); a plain basename rule
+/// is matched against the entry's own name at any depth below `base`.
+#[derive(Debug, Clone)]
+struct IgnorePattern {
+    regex: Regex,
+    negate: bool,
+    anchored: bool,
+    base: PathBuf,
+}
+
+/// A directory queued for traversal, carrying the ignore rules inherited
+/// from its ancestors so each worker can extend them with the directory's
+/// own `.gitignore`/`.ignore` once it gets there.
+struct DirTask {
+    path: PathBuf,
+    inherited_ignores: Vec<IgnorePattern>,
+}
+
+/// Shared state for the work-stealing directory walk: a queue of directories
+/// still to be visited, a count of directories queued or in-flight (used so
+/// workers can tell when there is truly nothing left to do), and the
+/// collected results.
+struct WalkState {
+    queue: Mutex<VecDeque<DirTask>>,
+    pending: AtomicUsize,
+    results: Mutex<Vec<FileInfo>>,
+}
+
+/// Content-search settings threaded through the walk so every worker
+/// searches file contents the same way.
+#[derive(Debug, Clone, Copy, Default)]
+struct ContentSearchOptions {
+    enabled: bool,
+    before_context: usize,
+    after_context: usize,
+    include_binary: bool,
+}
+
+/// Parses a `.gitignore`/`.ignore` file into compiled patterns, reusing the
+/// same glob-to-regex translation as pattern matching. `base` is the
+/// directory containing the ignore file, recorded on each pattern so
+/// anchored (multi-segment) rules can be matched against the right relative
+/// path later.
+fn parse_ignore_file(path: &Path) -> Vec<IgnorePattern> {
+    let mut patterns = Vec::new();
+
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return patterns,
+    };
+
+    let base = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+
+    for line in BufReader::new(file).lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => continue,
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (negate, rule) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = rule.starts_with('/') || rule.trim_end_matches('/').contains('/');
+        let rule = rule.trim_start_matches('/').trim_end_matches('/');
+        if rule.is_empty() {
+            continue;
+        }
+
+        if let Ok(regex) = Regex::new(&glob_to_regex(rule)) {
+            patterns.push(IgnorePattern {
+                regex,
+                negate,
+                anchored,
+                base: base.clone(),
+            });
+        }
+    }
+
+    patterns
+}
+
+/// Returns whether `path` (whose own name is `name`) is ignored by
+/// `patterns`, where later patterns take precedence over earlier ones (a
+/// `negate` pattern re-includes it). An anchored pattern is matched against
+/// `path`'s slash-separated path relative to the ignore file's directory; a
+/// plain basename pattern is matched against `name` alone, so it applies at
+/// any depth below that directory.
+fn is_ignored(path: &Path, name: &str, patterns: &[IgnorePattern]) -> bool {
+    let mut ignored = false;
+    for pattern in patterns {
+        let matches = if pattern.anchored {
+            path.strip_prefix(&pattern.base)
+                .ok()
+                .map(|relative| relative.to_string_lossy().replace(std::path::MAIN_SEPARATOR, "/"))
+                .is_some_and(|relative| pattern.regex.is_match(&relative))
+        } else {
+            pattern.regex.is_match(name)
+        };
+        if matches {
+            ignored = !pattern.negate;
+        }
+    }
+    ignored
 }
 
 fn main() {
     let args = Args::parse();
-    let regex_pattern = glob_to_regex(&args.pattern);
-    let regex = Regex::new(&regex_pattern).unwrap();
-    let mut results = find_files(&args.dir, &regex, &args);
+    let patterns: Vec<String> = args
+        .pattern
+        .iter()
+        .flat_map(|p| p.split(','))
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    let case_insensitive = if args.case_sensitive {
+        false
+    } else if args.ignore_case {
+        true
+    } else {
+        !patterns.iter().any(|p| pattern_has_uppercase_char(p))
+    };
+    let matcher = Matcher::build(&patterns, case_insensitive);
+    let filters = build_filters(&args);
+    let mut results = find_files(&args.dir, &matcher, &filters, &args);
 
     // Sort results
     if let Some(sort_by) = &args.sort {
         match sort_by.as_str() {
             "name" => results.sort_by(|a, b| a.path.file_name().cmp(&b.path.file_name())),
-            "size" => results.sort_by(|a, b| b.size.cmp(&a.size)),
-            "date" => results.sort_by(|a, b| b.modified.cmp(&a.modified)),
+            "size" => results.sort_by_key(|f| std::cmp::Reverse(f.size)),
+            "date" => results.sort_by_key(|f| std::cmp::Reverse(f.modified)),
             _ => {}
         }
     }
 
+    if let Some(template) = &args.exec {
+        run_exec(template, &results);
+        return;
+    }
+
+    if let Some(template) = &args.exec_batch {
+        run_exec_batch(template, &results);
+        return;
+    }
+
+    let use_color = match args.color.as_str() {
+        "always" => true,
+        "never" => false,
+        _ => std::io::stdout().is_terminal(),
+    };
+    let ls_colors =
+        use_color.then(|| LsColors::parse(&std::env::var("LS_COLORS").unwrap_or_default()));
+    let colorize_path = |path: &Path, text: &str| -> String {
+        match ls_colors.as_ref().and_then(|lc| lc.code_for(path)) {
+            Some(code) => paint(text, code),
+            None => text.to_string(),
+        }
+    };
+    let dim = |text: String| -> String {
+        if use_color {
+            paint(&text, DIM_CODE)
+        } else {
+            text
+        }
+    };
+    let highlight_match = |content_match: &ContentMatch| -> String {
+        match content_match.match_range {
+            Some((start, end)) if use_color => format!(
+                "{}{}{}",
+                &content_match.text[..start],
+                paint(&content_match.text[start..end], HIGHLIGHT_CODE),
+                &content_match.text[end..]
+            ),
+            _ => content_match.text.clone(),
+        }
+    };
+
     // Display results
     for file in results {
-        print!("Found: {:?}", file.path);
-
-        if args.date {
-            if let Ok(_) = file.modified.duration_since(SystemTime::UNIX_EPOCH) {
-                print!(
-                    ", Modified: {}",
-                    DateTime::<Utc>::from(file.modified).format("%Y-%m-%d %H:%M:%S")
+        if args.content_search && !file.content_matches.is_empty() {
+            for content_match in &file.content_matches {
+                let sep = if content_match.is_match { ':' } else { '-' };
+                println!(
+                    "{}{}{}{}{}",
+                    colorize_path(&file.path, &file.path.display().to_string()),
+                    sep,
+                    dim(content_match.line_no.to_string()),
+                    sep,
+                    highlight_match(content_match)
                 );
             }
+            continue;
         }
 
-        if args.size {
-            if file.path.is_file() {
-                if args.human_readable {
-                    print!(", Size: {}", human_readable_size(file.size));
-                } else {
-                    print!(", Size: {} bytes", file.size);
-                }
-            }
+        print!(
+            "Found: {}",
+            colorize_path(&file.path, &format!("{:?}", file.path))
+        );
+
+        if args.date && file.modified.duration_since(SystemTime::UNIX_EPOCH).is_ok() {
+            print!(
+                "{}",
+                dim(format!(
+                    ", Modified: {}",
+                    DateTime::<Utc>::from(file.modified).format("%Y-%m-%d %H:%M:%S")
+                ))
+            );
         }
 
-        if args.content_search && file.matches_content {
-            print!(", Matches content");
+        if args.show_size && file.path.is_file() {
+            let size_text = if args.human_readable {
+                format!(", Size: {}", human_readable_size(file.size))
+            } else {
+                format!(", Size: {} bytes", file.size)
+            };
+            print!("{}", dim(size_text));
         }
 
         println!();
     }
 }
 
-fn find_files(dir: &str, regex: &Regex, args: &Args) -> Vec<FileInfo> {
-    let mut results = Vec::new();
-
-    if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                let file_name = path.file_name().unwrap().to_string_lossy();
-
-                if regex.is_match(&file_name) || (args.content_search && path.is_file()) {
-                    if let Ok(metadata) = fs::metadata(&path) {
-                        let matches_content = if args.content_search && path.is_file() {
-                            search_file_content(&path, regex)
-                        } else {
-                            false
-                        };
-
-                        if regex.is_match(&file_name) || matches_content {
-                            results.push(FileInfo {
-                                path: path.clone(),
-                                size: metadata.len(),
-                                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
-                                matches_content,
-                            });
-                        }
-                    }
-                }
+/// Substitutes fd-style placeholders (`{}`, `{/}`, `{//}`, `{.}`, `{/.}`) in
+/// `token` with values derived from `path`.
+fn substitute_placeholders(token: &str, path: &Path) -> String {
+    let full = path.to_string_lossy();
+    let basename = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|| ".".to_string());
+    let without_ext = path.with_extension("").to_string_lossy().into_owned();
+    let basename_no_ext = Path::new(&basename)
+        .with_extension("")
+        .to_string_lossy()
+        .into_owned();
 
-                if path.is_dir() {
-                    results.extend(find_files(path.to_str().unwrap(), regex, args));
-                }
+    token
+        .replace("{//}", &parent)
+        .replace("{/.}", &basename_no_ext)
+        .replace("{/}", &basename)
+        .replace("{.}", &without_ext)
+        .replace("{}", &full)
+}
+
+const EXEC_PLACEHOLDERS: [&str; 5] = ["{}", "{/}", "{//}", "{.}", "{/.}"];
+
+/// Runs `template` once per match, substituting placeholders in every
+/// whitespace-separated token. If the template contains no placeholder, the
+/// matched path is appended as the final argument instead.
+fn run_exec(template: &str, results: &[FileInfo]) {
+    let has_placeholder = EXEC_PLACEHOLDERS.iter().any(|p| template.contains(p));
+
+    for file in results {
+        let mut tokens = template.split_whitespace();
+        let program = match tokens.next() {
+            Some(program) => substitute_placeholders(program, &file.path),
+            None => continue,
+        };
+
+        let mut command = std::process::Command::new(program);
+        for token in tokens {
+            command.arg(substitute_placeholders(token, &file.path));
+        }
+        if !has_placeholder {
+            command.arg(&file.path);
+        }
+
+        match command.status() {
+            Ok(status) if !status.success() => {
+                eprintln!("Command failed ({}) for {:?}", status, file.path)
             }
+            Err(err) => eprintln!("Failed to run command for {:?}: {}", file.path, err),
+            _ => {}
+        }
+    }
+}
+
+/// Runs `template` once with every matched path appended as arguments, or
+/// substituted at a single `{}` placeholder if the template contains one.
+fn run_exec_batch(template: &str, results: &[FileInfo]) {
+    if results.is_empty() {
+        return;
+    }
+
+    let mut tokens = template.split_whitespace();
+    let program = match tokens.next() {
+        Some(program) => program,
+        None => return,
+    };
+
+    let mut args: Vec<std::ffi::OsString> = Vec::new();
+    let mut has_placeholder = false;
+    for token in tokens {
+        if token == "{}" {
+            has_placeholder = true;
+            args.extend(results.iter().map(|f| f.path.clone().into_os_string()));
+        } else {
+            args.push(token.into());
         }
     }
+    if !has_placeholder {
+        args.extend(results.iter().map(|f| f.path.clone().into_os_string()));
+    }
+
+    let mut command = std::process::Command::new(program);
+    command.args(&args);
+
+    match command.status() {
+        Ok(status) if !status.success() => eprintln!("Command failed ({})", status),
+        Err(err) => eprintln!("Failed to run batch command: {}", err),
+        _ => {}
+    }
+}
+
+/// Walks `dir` using a pool of worker threads that share a queue of
+/// directories still to visit. Each worker pops a directory, scans its
+/// entries, pushes any matching files into the shared results and any
+/// subdirectories back onto the queue, until the queue is empty and no
+/// worker is still processing a directory.
+fn find_files(dir: &str, matcher: &Matcher, filters: &Filters, args: &Args) -> Vec<FileInfo> {
+    let num_threads = args.threads.unwrap_or_else(|| {
+        thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+
+    let (before_context, after_context) = match args.context {
+        Some(c) => (c, c),
+        None => (args.before_context, args.after_context),
+    };
+    let content_opts = ContentSearchOptions {
+        enabled: args.content_search,
+        before_context,
+        after_context,
+        include_binary: args.text,
+    };
+
+    let state = Arc::new(WalkState {
+        queue: Mutex::new(VecDeque::from([DirTask {
+            path: PathBuf::from(dir),
+            inherited_ignores: Vec::new(),
+        }])),
+        pending: AtomicUsize::new(1),
+        results: Mutex::new(Vec::new()),
+    });
+
+    let handles: Vec<_> = (0..num_threads.max(1))
+        .map(|_| {
+            let state = Arc::clone(&state);
+            let matcher = matcher.clone();
+            let filters = filters.clone();
+            let hidden = args.hidden;
+            let no_ignore = args.no_ignore;
+            thread::spawn(move || {
+                worker_loop(&state, &matcher, &filters, content_opts, hidden, no_ignore)
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    Arc::try_unwrap(state)
+        .unwrap_or_else(|_| panic!("worker threads did not release the shared walk state"))
+        .results
+        .into_inner()
+        .unwrap()
+}
 
-    results
+/// Repeatedly pops a directory off the shared queue and visits it until the
+/// queue is empty and no directory is still being processed by any worker.
+fn worker_loop(
+    state: &WalkState,
+    matcher: &Matcher,
+    filters: &Filters,
+    content_opts: ContentSearchOptions,
+    hidden: bool,
+    no_ignore: bool,
+) {
+    loop {
+        let task = state.queue.lock().unwrap().pop_front();
+        let task = match task {
+            Some(task) => task,
+            None => {
+                if state.pending.load(Ordering::SeqCst) == 0 {
+                    return;
+                }
+                thread::yield_now();
+                continue;
+            }
+        };
+
+        visit_dir(state, task, matcher, filters, content_opts, hidden, no_ignore);
+        state.pending.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
-fn search_file_content(path: &Path, regex: &Regex) -> bool {
-    if let Ok(file) = fs::File::open(path) {
-        let reader = BufReader::new(file);
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                if regex.is_match(&line) {
-                    return true;
+/// Scans a single directory, recording matches directly into the shared
+/// results and pushing any subdirectories back onto the shared queue. Hidden
+/// entries and paths matched by an inherited or local ignore file are
+/// skipped entirely (neither reported nor descended into) unless disabled.
+fn visit_dir(
+    state: &WalkState,
+    task: DirTask,
+    matcher: &Matcher,
+    filters: &Filters,
+    content_opts: ContentSearchOptions,
+    hidden: bool,
+    no_ignore: bool,
+) {
+    let DirTask {
+        path: dir,
+        mut inherited_ignores,
+    } = task;
+
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    if !no_ignore {
+        inherited_ignores.extend(parse_ignore_file(&dir.join(".gitignore")));
+        inherited_ignores.extend(parse_ignore_file(&dir.join(".ignore")));
+    }
+
+    let mut local_results = Vec::new();
+    let mut subdirs = Vec::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_name = match path.file_name() {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => continue,
+        };
+
+        if !hidden && file_name.starts_with('.') {
+            continue;
+        }
+        if !no_ignore && is_ignored(&path, &file_name, &inherited_ignores) {
+            continue;
+        }
+
+        if matcher.is_match(&file_name) || (content_opts.enabled && path.is_file()) {
+            if let Ok(metadata) = fs::metadata(&path) {
+                let content_matches = if content_opts.enabled && path.is_file() {
+                    search_file_content(
+                        &path,
+                        matcher,
+                        content_opts.before_context,
+                        content_opts.after_context,
+                        content_opts.include_binary,
+                    )
+                } else {
+                    Vec::new()
+                };
+
+                if (matcher.is_match(&file_name) || !content_matches.is_empty())
+                    && filters.passes(&path, &metadata)
+                {
+                    local_results.push(FileInfo {
+                        path: path.clone(),
+                        size: metadata.len(),
+                        modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                        content_matches,
+                    });
                 }
             }
         }
+
+        if path.is_dir() {
+            subdirs.push(DirTask {
+                path,
+                inherited_ignores: inherited_ignores.clone(),
+            });
+        }
+    }
+
+    if !subdirs.is_empty() {
+        state.pending.fetch_add(subdirs.len(), Ordering::SeqCst);
+        state.queue.lock().unwrap().extend(subdirs);
+    }
+
+    if !local_results.is_empty() {
+        state.results.lock().unwrap().extend(local_results);
     }
-    false
 }
 
-fn glob_to_regex(pattern: &str) -> String {
+/// Searches `path` for `matcher`, returning every matching line plus up to
+/// `before`/`after` lines of surrounding context. Files containing a NUL
+/// byte are treated as binary and skipped unless `include_binary` is set, so
+/// a stray binary file can't make the regex engine choke on invalid UTF-8.
+fn search_file_content(
+    path: &Path,
+    matcher: &Matcher,
+    before: usize,
+    after: usize,
+    include_binary: bool,
+) -> Vec<ContentMatch> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(_) => return Vec::new(),
+    };
+
+    if !include_binary && bytes.contains(&0) {
+        return Vec::new();
+    }
+
+    let text = String::from_utf8_lossy(&bytes);
+    let lines: Vec<&str> = text.lines().collect();
+
+    let matched_lines: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| matcher.matches_content(line))
+        .map(|(i, _)| i)
+        .collect();
+
+    if matched_lines.is_empty() {
+        return Vec::new();
+    }
+
+    let mut included = std::collections::BTreeSet::new();
+    for &i in &matched_lines {
+        let start = i.saturating_sub(before);
+        let end = (i + after).min(lines.len().saturating_sub(1));
+        included.extend(start..=end);
+    }
+
+    included
+        .into_iter()
+        .map(|i| {
+            let is_match = matched_lines.contains(&i);
+            ContentMatch {
+                line_no: i + 1,
+                text: lines[i].to_string(),
+                is_match,
+                match_range: is_match.then(|| matcher.find_content_match(lines[i])).flatten(),
+            }
+        })
+        .collect()
+}
+
+/// Translates a glob pattern into the body of a regex (no anchors), so
+/// callers can either anchor it for a whole-string match or leave it bare for
+/// a substring search.
+fn glob_to_regex_body(pattern: &str) -> String {
     let mut regex_pattern = String::new();
     let mut in_brackets = false;
 
@@ -204,7 +1108,203 @@ fn glob_to_regex(pattern: &str) -> String {
         }
     }
 
-    format!("^{}$", regex_pattern)
+    regex_pattern
+}
+
+fn glob_to_regex(pattern: &str) -> String {
+    format!("^{}$", glob_to_regex_body(pattern))
+}
+
+/// How a single raw `--pattern` argument was classified for matching.
+enum PatternKind {
+    /// No glob metacharacters at all: matches only by exact equality.
+    Literal(String),
+    /// A `*.ext` glob with a literal extension: matches by exact suffix.
+    ExtensionSuffix(String),
+    /// Anything else: compiled through `glob_to_regex` and run as regex.
+    Regex(String),
+}
+
+fn classify_pattern(pattern: &str) -> PatternKind {
+    const GLOB_METACHARS: [char; 4] = ['*', '?', '[', ']'];
+
+    if let Some(ext) = pattern.strip_prefix("*.") {
+        if !ext.contains(GLOB_METACHARS) {
+            return PatternKind::ExtensionSuffix(format!(".{}", ext));
+        }
+    }
+
+    if !pattern.contains(GLOB_METACHARS) {
+        return PatternKind::Literal(pattern.to_string());
+    }
+
+    PatternKind::Regex(pattern.to_string())
+}
+
+/// Matches a filename (or line of content) against one or more patterns,
+/// following ripgrep's glob-set approach: pure-literal patterns are checked
+/// with a `HashSet` lookup, literal `*.ext` patterns with an Aho-Corasick
+/// automaton, and everything else with a `RegexSet`. Each filename is
+/// checked against the cheapest bucket first, keeping per-entry cost near
+/// constant even with many patterns.
+///
+/// Filename matching (`is_match`) requires the whole name to match. Content
+/// search (`matches_content`) instead looks for any of the patterns
+/// occurring anywhere in the line, like `grep`.
+#[derive(Clone)]
+struct Matcher {
+    case_insensitive: bool,
+    literals: HashSet<String>,
+    extension_suffixes: AhoCorasick,
+    regex_set: RegexSet,
+    content_regex_set: RegexSet,
+    content_regexes: Vec<Regex>,
+}
+
+impl Matcher {
+    fn build(patterns: &[String], case_insensitive: bool) -> Matcher {
+        let mut literals = HashSet::new();
+        let mut extension_suffixes = Vec::new();
+        let mut raw_regex_patterns = Vec::new();
+
+        for pattern in patterns {
+            match classify_pattern(pattern) {
+                PatternKind::Literal(literal) => {
+                    literals.insert(normalize_case(&literal, case_insensitive));
+                }
+                PatternKind::ExtensionSuffix(suffix) => {
+                    extension_suffixes.push(normalize_case(&suffix, case_insensitive));
+                }
+                PatternKind::Regex(pattern) => {
+                    raw_regex_patterns.push(pattern);
+                }
+            }
+        }
+
+        let extension_suffixes = AhoCorasickBuilder::new()
+            .ascii_case_insensitive(case_insensitive)
+            .build(&extension_suffixes)
+            .unwrap_or_else(|_| {
+                eprintln!("Invalid --pattern: could not build extension matcher");
+                std::process::exit(1);
+            });
+
+        let anchored_patterns: Vec<String> =
+            raw_regex_patterns.iter().map(|p| glob_to_regex(p)).collect();
+        let regex_set = RegexSetBuilder::new(&anchored_patterns)
+            .case_insensitive(case_insensitive)
+            .build()
+            .unwrap_or_else(|err| {
+                eprintln!("Invalid --pattern: {}", err);
+                std::process::exit(1);
+            });
+
+        let unanchored_patterns: Vec<String> = raw_regex_patterns
+            .iter()
+            .map(|p| glob_to_regex_body(p))
+            .collect();
+        let content_regex_set = RegexSetBuilder::new(&unanchored_patterns)
+            .case_insensitive(case_insensitive)
+            .build()
+            .unwrap_or_else(|err| {
+                eprintln!("Invalid --pattern: {}", err);
+                std::process::exit(1);
+            });
+        let content_regexes = unanchored_patterns
+            .iter()
+            .map(|p| {
+                RegexBuilder::new(p)
+                    .case_insensitive(case_insensitive)
+                    .build()
+                    .unwrap_or_else(|err| {
+                        eprintln!("Invalid --pattern: {}", err);
+                        std::process::exit(1);
+                    })
+            })
+            .collect();
+
+        Matcher {
+            case_insensitive,
+            literals,
+            extension_suffixes,
+            regex_set,
+            content_regex_set,
+            content_regexes,
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        let probe = normalize_case(text, self.case_insensitive);
+
+        if self.literals.contains(&probe) {
+            return true;
+        }
+
+        if self
+            .extension_suffixes
+            .find_iter(&probe)
+            .any(|m| m.end() == probe.len())
+        {
+            return true;
+        }
+
+        self.regex_set.is_match(text)
+    }
+
+    /// Returns whether any pattern occurs anywhere within `text`, used for
+    /// grep-style content search rather than whole-string filename matching.
+    fn matches_content(&self, text: &str) -> bool {
+        let probe = normalize_case(text, self.case_insensitive);
+
+        if self.literals.iter().any(|literal| probe.contains(literal)) {
+            return true;
+        }
+
+        if self.extension_suffixes.is_match(&probe) {
+            return true;
+        }
+
+        self.content_regex_set.is_match(text)
+    }
+
+    /// Finds the byte range of the first pattern occurrence in `text`, used
+    /// to highlight a content match. Only meaningful for text already known
+    /// to match via `matches_content`.
+    fn find_content_match(&self, text: &str) -> Option<(usize, usize)> {
+        let probe = normalize_case(text, self.case_insensitive);
+
+        let literal_hit = self
+            .literals
+            .iter()
+            .filter_map(|literal| probe.find(literal.as_str()).map(|start| (start, literal.len())))
+            .map(|(start, len)| (start, start + len))
+            .min_by_key(|&(start, _)| start);
+
+        let suffix_hit = self
+            .extension_suffixes
+            .find_iter(&probe)
+            .next()
+            .map(|m| (m.start(), m.end()));
+
+        let regex_hit = self
+            .content_regexes
+            .iter()
+            .filter_map(|re| re.find(text).map(|m| (m.start(), m.end())))
+            .min_by_key(|&(start, _)| start);
+
+        [literal_hit, suffix_hit, regex_hit]
+            .into_iter()
+            .flatten()
+            .min_by_key(|&(start, _)| start)
+    }
+}
+
+fn normalize_case(text: &str, case_insensitive: bool) -> String {
+    if case_insensitive {
+        text.to_lowercase()
+    } else {
+        text.to_string()
+    }
 }
 
 fn human_readable_size(size: u64) -> String {
@@ -217,3 +1317,101 @@ fn human_readable_size(size: u64) -> String {
     let s = (size as f64) / p;
     format!("{:.2} {}", s, UNITS[i])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_size_expr_reads_sign_and_unit() {
+        assert!(matches!(
+            parse_size_expr("+10M"),
+            Some(SizeBound::AtLeast(n)) if n == 10 * 1024 * 1024
+        ));
+        assert!(matches!(
+            parse_size_expr("-500k"),
+            Some(SizeBound::AtMost(n)) if n == 500 * 1024
+        ));
+        assert!(matches!(
+            parse_size_expr("1G"),
+            Some(SizeBound::Exact(n)) if n == 1024 * 1024 * 1024
+        ));
+        assert!(matches!(parse_size_expr("500"), Some(SizeBound::Exact(500))));
+    }
+
+    #[test]
+    fn parse_size_expr_rejects_unknown_units() {
+        assert!(parse_size_expr("+10Q").is_none());
+        assert!(parse_size_expr("not-a-size").is_none());
+    }
+
+    #[test]
+    fn parse_relative_duration_reads_number_and_unit() {
+        assert_eq!(parse_relative_duration("2d"), Some(Duration::from_secs(2 * 86400)));
+        assert_eq!(parse_relative_duration("3h"), Some(Duration::from_secs(3 * 3600)));
+        assert_eq!(parse_relative_duration("45m"), Some(Duration::from_secs(45 * 60)));
+        assert_eq!(parse_relative_duration("bogus"), None);
+        assert_eq!(parse_relative_duration("d"), None);
+    }
+
+    #[test]
+    fn parse_time_expr_accepts_duration_or_absolute_date() {
+        assert!(parse_time_expr("2d").is_some());
+        assert!(parse_time_expr("2024-01-01").is_some());
+        assert!(parse_time_expr("not-a-time").is_none());
+    }
+
+    fn temp_ignore_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rust_file_finder_test_{}_{}_{}",
+            name,
+            std::process::id(),
+            name.len()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn anchored_ignore_rule_matches_relative_path_not_basename() {
+        let dir = temp_ignore_dir("anchored");
+        fs::write(dir.join(".gitignore"), "src/generated\n").unwrap();
+        let patterns = parse_ignore_file(&dir.join(".gitignore"));
+
+        let generated_dir = dir.join("src").join("generated");
+        assert!(is_ignored(&generated_dir, "generated", &patterns));
+
+        let unrelated_dir = dir.join("other").join("generated");
+        assert!(!is_ignored(&unrelated_dir, "generated", &patterns));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn plain_ignore_rule_matches_basename_at_any_depth() {
+        let dir = temp_ignore_dir("basename");
+        fs::write(dir.join(".gitignore"), "*.log\n").unwrap();
+        let patterns = parse_ignore_file(&dir.join(".gitignore"));
+
+        let nested_log = dir.join("a").join("b").join("app.log");
+        assert!(is_ignored(&nested_log, "app.log", &patterns));
+
+        let nested_txt = dir.join("a").join("b").join("app.txt");
+        assert!(!is_ignored(&nested_txt, "app.txt", &patterns));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn negated_ignore_rule_reincludes_a_match() {
+        let dir = temp_ignore_dir("negate");
+        fs::write(dir.join(".gitignore"), "*.log\n!keep.log\n").unwrap();
+        let patterns = parse_ignore_file(&dir.join(".gitignore"));
+
+        assert!(is_ignored(&dir.join("app.log"), "app.log", &patterns));
+        assert!(!is_ignored(&dir.join("keep.log"), "keep.log", &patterns));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}